@@ -0,0 +1,119 @@
+//! Headless access to AIOS Chat history: list threads, read messages, export
+//! a conversation, or delete a thread, all against the same `chat.db` the
+//! Tauri app uses.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+
+/// Bundle identifier the Tauri app stores `chat.db` under; used to resolve
+/// the default database path the same way the app's data directory does.
+const APP_IDENTIFIER: &str = "com.aios.chat";
+
+#[derive(Parser)]
+#[command(name = "aios", about = "Inspect and export AIOS Chat history")]
+struct Cli {
+    /// Path to chat.db. Defaults to the Tauri app's data directory.
+    #[arg(long, global = true)]
+    db: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Work with threads
+    Threads {
+        #[command(subcommand)]
+        command: ThreadsCommand,
+    },
+    /// Work with messages
+    Messages {
+        #[command(subcommand)]
+        command: MessagesCommand,
+    },
+    /// Export a thread's messages
+    Export {
+        thread_id: String,
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+    },
+    /// Delete a thread and its messages
+    DeleteThread { id: String },
+}
+
+#[derive(Subcommand)]
+enum ThreadsCommand {
+    /// List every thread, most recently updated first
+    List,
+}
+
+#[derive(Subcommand)]
+enum MessagesCommand {
+    /// Show every message in a thread, oldest first
+    Show { thread_id: String },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Json,
+    Markdown,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let db_path = cli.db.unwrap_or_else(default_db_path);
+    let db = aios_db::Database::new(db_path).context("failed to open chat.db")?;
+    let conn = db.conn();
+    let key = db.key();
+
+    match cli.command {
+        Command::Threads {
+            command: ThreadsCommand::List,
+        } => {
+            for thread in aios_db::threads::list_threads(&conn, key)? {
+                println!(
+                    "{}\t{}\t{}",
+                    thread.id,
+                    thread.title.as_deref().unwrap_or("(untitled)"),
+                    thread.updated_at
+                );
+            }
+        }
+        Command::Messages {
+            command: MessagesCommand::Show { thread_id },
+        } => {
+            for message in aios_db::messages::get_messages(&conn, &thread_id, key)? {
+                println!("[{}] {}: {}", message.created_at, message.role, message.content);
+            }
+        }
+        Command::Export { thread_id, format } => {
+            let messages = aios_db::messages::get_messages(&conn, &thread_id, key)?;
+            match format {
+                ExportFormat::Json => println!("{}", serde_json::to_string_pretty(&messages)?),
+                ExportFormat::Markdown => {
+                    for message in messages {
+                        println!(
+                            "**{}** _{}_\n\n{}\n",
+                            message.role, message.created_at, message.content
+                        );
+                    }
+                }
+            }
+        }
+        Command::DeleteThread { id } => {
+            let search_conn = db.search_index();
+            aios_db::threads::delete_thread(&conn, &id, &search_conn)?;
+            println!("deleted thread {id}");
+        }
+    }
+
+    Ok(())
+}
+
+fn default_db_path() -> PathBuf {
+    let base = dirs::data_dir().expect("could not determine the platform data directory");
+    base.join(APP_IDENTIFIER).join("chat.db")
+}
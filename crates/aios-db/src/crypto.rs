@@ -0,0 +1,73 @@
+//! AES-256-GCM encryption for data at rest.
+//!
+//! Each encrypted field is stored as `base64(nonce || ciphertext || tag)` in
+//! the existing TEXT columns. A fresh random 12-byte nonce is drawn for every
+//! call to [`encrypt`]; nonces are never reused across fields or rows.
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+const NONCE_LEN: usize = 12;
+
+/// Encrypt `plaintext` with `key`, returning `base64(nonce || ciphertext || tag)`.
+pub fn encrypt(key: &[u8; 32], plaintext: &str) -> Result<String> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| anyhow!("failed to encrypt field"))?;
+
+    let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(STANDARD.encode(out))
+}
+
+/// Decrypt a value produced by [`encrypt`]. Fails clearly (rather than
+/// silently falling back to some default) if the data is truncated,
+/// corrupted, or was encrypted under a different key.
+pub fn decrypt(key: &[u8; 32], encoded: &str) -> Result<String> {
+    let raw = STANDARD
+        .decode(encoded)
+        .map_err(|_| anyhow!("failed to base64-decode encrypted field"))?;
+
+    if raw.len() < NONCE_LEN {
+        return Err(anyhow!("encrypted field is truncated"));
+    }
+    let (nonce_bytes, ciphertext) = raw.split_at(NONCE_LEN);
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| anyhow!("failed to decrypt field: wrong key or corrupted data"))?;
+
+    String::from_utf8(plaintext).map_err(|_| anyhow!("decrypted field is not valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_plaintext() {
+        let key = [7u8; 32];
+        let encoded = encrypt(&key, "hello, world").unwrap();
+        assert_eq!(decrypt(&key, &encoded).unwrap(), "hello, world");
+    }
+
+    #[test]
+    fn decrypt_fails_with_wrong_key() {
+        let encoded = encrypt(&[1u8; 32], "secret").unwrap();
+        assert!(decrypt(&[2u8; 32], &encoded).is_err());
+    }
+
+    #[test]
+    fn decrypt_fails_on_truncated_data() {
+        let key = [7u8; 32];
+        assert!(decrypt(&key, &STANDARD.encode(b"too short")).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+//! Shared chat history storage: the SQLite schema, message/thread access, and
+//! at-rest encryption, usable from both the Tauri app and the headless CLI.
+
+pub(crate) mod crypto;
+pub mod messages;
+pub mod search;
+mod schema;
+pub mod threads;
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rusqlite::Connection;
+
+pub use messages::{Message, NewMessage};
+pub use threads::Thread;
+
+/// Keyring service name; matches the one `credentials::get_credential` uses
+/// for user-facing API keys, so both land in the same OS credential store.
+const KEYRING_SERVICE: &str = "com.aios.chat";
+/// Not a user-facing credential: never surfaced through `get_all_credentials`.
+const DB_ENCRYPTION_KEY: &str = "db_encryption_key";
+
+pub struct Database {
+    conn: Mutex<Connection>,
+    /// The full-text search index; a private `:memory:` connection, never
+    /// the one backing `chat.db`. See [`search`].
+    search_index: Mutex<Connection>,
+    encryption_key: [u8; 32],
+}
+
+impl Database {
+    pub fn new(path: PathBuf) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        schema::init(&conn)?;
+        let encryption_key = load_or_create_encryption_key()?;
+
+        let search_index = Connection::open_in_memory()
+            .context("failed to open in-memory search index")?;
+        search::init_index(&search_index)?;
+        search::backfill(&search_index, &conn, &encryption_key)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            search_index: Mutex::new(search_index),
+            encryption_key,
+        })
+    }
+
+    pub fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn.lock().expect("Database mutex poisoned")
+    }
+
+    /// The in-memory full-text search index; see [`search`].
+    pub fn search_index(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.search_index.lock().expect("search index mutex poisoned")
+    }
+
+    /// The AES-256-GCM data key used to encrypt message/thread content at rest.
+    pub fn key(&self) -> &[u8; 32] {
+        &self.encryption_key
+    }
+}
+
+/// Load the database's at-rest encryption key from the OS keychain,
+/// generating and persisting a new random 32-byte key on first run.
+fn load_or_create_encryption_key() -> Result<[u8; 32]> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, DB_ENCRYPTION_KEY)?;
+
+    match entry.get_password() {
+        Ok(encoded) => STANDARD
+            .decode(encoded)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("stored db encryption key has the wrong length")),
+        Err(keyring::Error::NoEntry) => {
+            use aes_gcm::aead::OsRng;
+            use aes_gcm::{Aes256Gcm, KeyInit};
+
+            let key = Aes256Gcm::generate_key(OsRng);
+            entry
+                .set_password(&STANDARD.encode(key))
+                .context("failed to persist db encryption key")?;
+            Ok(key.into())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,228 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::crypto;
+use super::search;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+    pub id: String,
+    pub thread_id: String,
+    pub role: String,
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_invocations: Option<Vec<Value>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewMessage {
+    pub role: String,
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_invocations: Option<Vec<Value>>,
+}
+
+pub fn save_message(
+    conn: &Connection,
+    thread_id: &str,
+    message: &NewMessage,
+    key: &[u8; 32],
+    search_conn: &Connection,
+) -> Result<Message> {
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now();
+    let now_str = now.to_rfc3339();
+
+    // Serialize tool_invocations to JSON string if present
+    let tool_invocations_json: Option<String> = message
+        .tool_invocations
+        .as_ref()
+        .map(|ti| serde_json::to_string(ti).unwrap_or_default());
+
+    let encrypted_content = crypto::encrypt(key, &message.content)?;
+    let encrypted_tool_invocations = tool_invocations_json
+        .as_deref()
+        .map(|json| crypto::encrypt(key, json))
+        .transpose()?;
+
+    conn.execute(
+        "INSERT INTO messages (id, thread_id, role, content, tool_invocations, encrypted, created_at) VALUES (?1, ?2, ?3, ?4, ?5, 1, ?6)",
+        params![id, thread_id, message.role, encrypted_content, encrypted_tool_invocations, now_str],
+    )?;
+
+    // Update thread timestamp
+    super::threads::update_thread_timestamp(conn, thread_id)?;
+
+    search::index_message(search_conn, &id, thread_id, &message.content)?;
+
+    Ok(Message {
+        id,
+        thread_id: thread_id.to_string(),
+        role: message.role.clone(),
+        content: message.content.clone(),
+        tool_invocations: message.tool_invocations.clone(),
+        created_at: now,
+    })
+}
+
+pub fn get_messages(conn: &Connection, thread_id: &str, key: &[u8; 32]) -> Result<Vec<Message>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, thread_id, role, content, tool_invocations, encrypted, created_at FROM messages WHERE thread_id = ?1 ORDER BY created_at ASC",
+    )?;
+
+    let rows = stmt
+        .query_map(params![thread_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, bool>(5)?,
+                row.get::<_, String>(6)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut messages = Vec::with_capacity(rows.len());
+    for (id, thread_id, role, content, tool_invocations_raw, encrypted, created_at_str) in rows {
+        // Legacy rows written before encryption support landed are plaintext;
+        // decrypt (or, for those, migrate to encrypted storage) transparently.
+        let (content, tool_invocations_json) = if encrypted {
+            let content = crypto::decrypt(key, &content)?;
+            let tool_invocations_json = tool_invocations_raw
+                .as_deref()
+                .map(|enc| crypto::decrypt(key, enc))
+                .transpose()?;
+            (content, tool_invocations_json)
+        } else {
+            let encrypted_content = crypto::encrypt(key, &content)?;
+            let encrypted_tool_invocations = tool_invocations_raw
+                .as_deref()
+                .map(|json| crypto::encrypt(key, json))
+                .transpose()?;
+            conn.execute(
+                "UPDATE messages SET content = ?1, tool_invocations = ?2, encrypted = 1 WHERE id = ?3",
+                params![encrypted_content, encrypted_tool_invocations, id],
+            )?;
+            (content, tool_invocations_raw)
+        };
+
+        let tool_invocations: Option<Vec<Value>> = tool_invocations_json
+            .and_then(|json| serde_json::from_str(&json).ok());
+
+        messages.push(Message {
+            id,
+            thread_id,
+            role,
+            content,
+            tool_invocations,
+            created_at: created_at_str.parse().unwrap_or_else(|_| Utc::now()),
+        });
+    }
+
+    Ok(messages)
+}
+
+pub fn delete_message(conn: &Connection, message_id: &str, search_conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM messages WHERE id = ?1", params![message_id])?;
+    search::remove_message(search_conn, message_id)?;
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub fn delete_messages_by_thread(
+    conn: &Connection,
+    thread_id: &str,
+    search_conn: &Connection,
+) -> Result<()> {
+    conn.execute(
+        "DELETE FROM messages WHERE thread_id = ?1",
+        params![thread_id],
+    )?;
+    search::remove_messages_by_thread(search_conn, thread_id)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threads::create_thread;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::schema::init(&conn).unwrap();
+        conn
+    }
+
+    fn test_search_conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        search::init_index(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn save_and_get_round_trip() {
+        let conn = test_db();
+        let search_conn = test_search_conn();
+        let key = [3u8; 32];
+        let thread = create_thread(&conn).unwrap();
+
+        let saved = save_message(
+            &conn,
+            &thread.id,
+            &NewMessage {
+                role: "user".to_string(),
+                content: "hello".to_string(),
+                tool_invocations: None,
+            },
+            &key,
+            &search_conn,
+        )
+        .unwrap();
+
+        let messages = get_messages(&conn, &thread.id, &key).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id, saved.id);
+        assert_eq!(messages[0].content, "hello");
+    }
+
+    #[test]
+    fn legacy_plaintext_row_migrates_transparently_on_read() {
+        let conn = test_db();
+        let key = [3u8; 32];
+        let thread = create_thread(&conn).unwrap();
+
+        // Simulate a row written before encryption support landed: plaintext
+        // content, `encrypted = 0`.
+        conn.execute(
+            "INSERT INTO messages (id, thread_id, role, content, encrypted, created_at) VALUES ('legacy-1', ?1, 'user', 'old plaintext', 0, '2020-01-01T00:00:00Z')",
+            params![thread.id],
+        )
+        .unwrap();
+
+        let messages = get_messages(&conn, &thread.id, &key).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].content, "old plaintext");
+
+        let encrypted: bool = conn
+            .query_row(
+                "SELECT encrypted FROM messages WHERE id = 'legacy-1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(encrypted, "legacy row should be migrated to encrypted storage on read");
+
+        // Reading again with the now-encrypted row should still decrypt cleanly.
+        let messages = get_messages(&conn, &thread.id, &key).unwrap();
+        assert_eq!(messages[0].content, "old plaintext");
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::Result;
+use rusqlite::Connection;
+
+/// One step in the schema's migration history. Migrations are numbered by
+/// their position in `MIGRATIONS` (1-indexed) and are tracked via SQLite's
+/// `PRAGMA user_version`, so adding a new column or table is as simple as
+/// appending a new entry here.
+type Migration = fn(&Connection) -> Result<()>;
+
+const MIGRATIONS: &[Migration] = &[
+    migration_001_initial_schema,
+    migration_002_tool_invocations,
+    migration_003_encryption_flags,
+    migration_004_settings_submissions,
+    migration_005_drop_persisted_search_index,
+];
+
+pub fn init(conn: &Connection) -> Result<()> {
+    conn.execute("PRAGMA foreign_keys = ON", [])?;
+
+    let current_version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate() {
+        let version = (index + 1) as u32;
+        if version <= current_version {
+            continue;
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        migration(&tx)?;
+        tx.pragma_update(None, "user_version", version)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+fn migration_001_initial_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r"
+        CREATE TABLE IF NOT EXISTS threads (
+            id TEXT PRIMARY KEY,
+            title TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS messages (
+            id TEXT PRIMARY KEY,
+            thread_id TEXT NOT NULL,
+            role TEXT NOT NULL,
+            content TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (thread_id) REFERENCES threads(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_messages_thread_id ON messages(thread_id);
+        ",
+    )?;
+    Ok(())
+}
+
+fn migration_002_tool_invocations(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "messages", "tool_invocations", "TEXT")
+}
+
+fn migration_003_encryption_flags(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "messages", "encrypted", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "threads", "encrypted", "INTEGER NOT NULL DEFAULT 0")
+}
+
+fn migration_004_settings_submissions(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r"
+        CREATE TABLE IF NOT EXISTS settings_submissions (
+            tool_call_id TEXT NOT NULL,
+            settings_key TEXT NOT NULL,
+            submitted_at TEXT NOT NULL,
+            PRIMARY KEY (tool_call_id, settings_key)
+        );
+        ",
+    )?;
+    Ok(())
+}
+
+fn migration_005_drop_persisted_search_index(conn: &Connection) -> Result<()> {
+    // An earlier revision created FTS5 tables here to back full-text search.
+    // That stored message/thread-title plaintext on disk right next to the
+    // AES-256-GCM ciphertext it's supposed to protect, defeating at-rest
+    // encryption. The search index now lives only in a private `:memory:`
+    // connection (see `crate::search`); drop any on-disk tables left over
+    // from that revision.
+    conn.execute_batch(
+        r"
+        DROP TABLE IF EXISTS messages_fts;
+        DROP TABLE IF EXISTS threads_fts;
+        ",
+    )?;
+    Ok(())
+}
+
+/// Add a column to `table` if it isn't already there.
+///
+/// Migrations stay idempotent this way even for databases that picked up a
+/// column earlier via this crate's pre-`user_version` ad-hoc ALTER checks,
+/// since those never recorded a migration version.
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    ddl_type: &str,
+) -> Result<()> {
+    let exists: bool = conn
+        .prepare(&format!(
+            "SELECT COUNT(*) FROM pragma_table_info('{table}') WHERE name='{column}'"
+        ))?
+        .query_row([], |row| row.get::<_, i32>(0))
+        .map(|count| count > 0)?;
+
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {table} ADD COLUMN {column} {ddl_type}"),
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init_leaves_user_version_at_migration_count() {
+        let conn = Connection::open_in_memory().unwrap();
+        init(&conn).unwrap();
+
+        let version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, MIGRATIONS.len() as u32);
+    }
+
+    #[test]
+    fn init_is_idempotent() {
+        let conn = Connection::open_in_memory().unwrap();
+        init(&conn).unwrap();
+        init(&conn).unwrap();
+
+        let version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, MIGRATIONS.len() as u32);
+    }
+
+    #[test]
+    fn migration_005_drops_legacy_on_disk_fts_tables() {
+        let conn = Connection::open_in_memory().unwrap();
+
+        // Simulate an install that already ran migrations 1-4 and the
+        // original (plaintext-leaking) migration 5, which created these
+        // tables on disk instead of in the in-memory search index.
+        for migration in &MIGRATIONS[..4] {
+            migration(&conn).unwrap();
+        }
+        conn.execute_batch(
+            r"
+            CREATE VIRTUAL TABLE messages_fts USING fts5(content, message_id UNINDEXED, thread_id UNINDEXED);
+            CREATE VIRTUAL TABLE threads_fts USING fts5(title, thread_id UNINDEXED);
+            ",
+        )
+        .unwrap();
+        conn.pragma_update(None, "user_version", 4u32).unwrap();
+
+        init(&conn).unwrap();
+
+        let fts_tables: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name IN ('messages_fts', 'threads_fts')",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(fts_tables, 0, "legacy on-disk FTS5 tables should be dropped");
+    }
+}
@@ -0,0 +1,211 @@
+//! In-memory full-text search over message content and thread titles.
+//!
+//! `messages.content` and `threads.title` are encrypted at rest
+//! ([`crate::crypto`]), so the index can't live in `chat.db` next to them
+//! without putting plaintext back on disk. Instead it lives only in a
+//! private `:memory:` connection ([`crate::Database::search_index`]) that's
+//! rebuilt from decrypted rows on process start ([`backfill`]) and kept in
+//! sync at the call sites that already touch plaintext — `save_message`,
+//! `delete_message`/`delete_messages_by_thread`, `update_thread_title`, and
+//! `delete_thread`. It never survives a restart, and it's never written
+//! anywhere but this connection.
+
+use anyhow::Result;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+/// A single search hit: which message it is, the thread it belongs to, and
+/// a highlighted snippet of the matching text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    pub message_id: String,
+    pub thread_id: String,
+    pub snippet: String,
+}
+
+/// Create the FTS5 tables on a fresh `:memory:` connection. Called once,
+/// when the database is opened.
+pub(crate) fn init_index(search_conn: &Connection) -> Result<()> {
+    search_conn.execute_batch(
+        r"
+        CREATE VIRTUAL TABLE messages_fts USING fts5(
+            content,
+            message_id UNINDEXED,
+            thread_id UNINDEXED
+        );
+
+        CREATE VIRTUAL TABLE threads_fts USING fts5(
+            title,
+            thread_id UNINDEXED
+        );
+        ",
+    )?;
+    Ok(())
+}
+
+/// Search message content, most relevant first.
+///
+/// `query` is treated as a literal phrase rather than FTS5 query syntax, so
+/// ordinary text containing quotes, hyphens, or reserved keywords (`AND`,
+/// `NOT`, ...) matches instead of raising a query-syntax error.
+pub fn search_messages(search_conn: &Connection, query: &str) -> Result<Vec<SearchResult>> {
+    let phrase_query = format!("\"{}\"", query.replace('"', "\"\""));
+
+    let mut stmt = search_conn.prepare(
+        "SELECT message_id, thread_id, snippet(messages_fts, 0, '<mark>', '</mark>', '…', 8)
+         FROM messages_fts
+         WHERE messages_fts MATCH ?1
+         ORDER BY bm25(messages_fts)
+         LIMIT 50",
+    )?;
+
+    let results = stmt
+        .query_map(params![phrase_query], |row| {
+            Ok(SearchResult {
+                message_id: row.get(0)?,
+                thread_id: row.get(1)?,
+                snippet: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+pub(crate) fn index_message(
+    search_conn: &Connection,
+    message_id: &str,
+    thread_id: &str,
+    content: &str,
+) -> Result<()> {
+    search_conn.execute(
+        "INSERT INTO messages_fts (content, message_id, thread_id) VALUES (?1, ?2, ?3)",
+        params![content, message_id, thread_id],
+    )?;
+    Ok(())
+}
+
+pub(crate) fn remove_message(search_conn: &Connection, message_id: &str) -> Result<()> {
+    search_conn.execute(
+        "DELETE FROM messages_fts WHERE message_id = ?1",
+        params![message_id],
+    )?;
+    Ok(())
+}
+
+pub(crate) fn remove_messages_by_thread(search_conn: &Connection, thread_id: &str) -> Result<()> {
+    search_conn.execute(
+        "DELETE FROM messages_fts WHERE thread_id = ?1",
+        params![thread_id],
+    )?;
+    Ok(())
+}
+
+pub(crate) fn index_thread_title(search_conn: &Connection, thread_id: &str, title: &str) -> Result<()> {
+    remove_thread(search_conn, thread_id)?;
+    search_conn.execute(
+        "INSERT INTO threads_fts (title, thread_id) VALUES (?1, ?2)",
+        params![title, thread_id],
+    )?;
+    Ok(())
+}
+
+pub(crate) fn remove_thread(search_conn: &Connection, thread_id: &str) -> Result<()> {
+    search_conn.execute(
+        "DELETE FROM threads_fts WHERE thread_id = ?1",
+        params![thread_id],
+    )?;
+    Ok(())
+}
+
+/// Populate the in-memory index from existing (encrypted-at-rest) history.
+///
+/// Called once, right after the database and its encryption key are ready,
+/// since the index starts out empty on every process start.
+pub(crate) fn backfill(search_conn: &Connection, conn: &Connection, key: &[u8; 32]) -> Result<()> {
+    for thread in crate::threads::list_threads(conn, key)? {
+        if let Some(title) = &thread.title {
+            index_thread_title(search_conn, &thread.id, title)?;
+        }
+        for message in crate::messages::get_messages(conn, &thread.id, key)? {
+            index_message(search_conn, &message.id, &thread.id, &message.content)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{save_message, NewMessage};
+    use crate::threads::{create_thread, update_thread_title};
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::schema::init(&conn).unwrap();
+        conn
+    }
+
+    fn test_search_conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init_index(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn backfill_rebuilds_index_from_encrypted_history() {
+        let conn = test_db();
+        let key = [7u8; 32];
+        let write_search_conn = test_search_conn();
+
+        let thread = create_thread(&conn).unwrap();
+        update_thread_title(&conn, &thread.id, "project kickoff", &key, &write_search_conn).unwrap();
+        save_message(
+            &conn,
+            &thread.id,
+            &NewMessage {
+                role: "user".to_string(),
+                content: "supersecretplaintext hello".to_string(),
+                tool_invocations: None,
+            },
+            &key,
+            &write_search_conn,
+        )
+        .unwrap();
+
+        // A fresh connection standing in for a new process start: empty
+        // until `backfill` repopulates it from the encrypted-at-rest rows.
+        let fresh_search_conn = test_search_conn();
+        backfill(&fresh_search_conn, &conn, &key).unwrap();
+
+        let results = search_messages(&fresh_search_conn, "supersecretplaintext hello").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].thread_id, thread.id);
+
+        let on_disk_content: String = conn
+            .query_row("SELECT content FROM messages", [], |row| row.get(0))
+            .unwrap();
+        assert_ne!(
+            on_disk_content, "supersecretplaintext hello",
+            "content backing the index must stay encrypted on disk"
+        );
+    }
+
+    #[test]
+    fn search_messages_treats_query_as_a_literal_phrase() {
+        let search_conn = test_search_conn();
+        index_message(&search_conn, "msg-1", "thread-1", "weather forecast: rain AND wind").unwrap();
+
+        for query in ["weather AND wind", "forecast: rain", "say \"hello\" to no one"] {
+            index_message(&search_conn, "msg-2", "thread-1", query).unwrap();
+            let results = search_messages(&search_conn, query).unwrap();
+            assert!(
+                !results.is_empty(),
+                "query `{query}` containing FTS5-special syntax should still match literally"
+            );
+            remove_message(&search_conn, "msg-2").unwrap();
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Identifies the OS process on the other end of a loopback connection.
+
+use std::net::SocketAddr;
+
+use anyhow::{anyhow, Context, Result};
+use netstat2::{iterate_sockets_info, AddressFamilyFlags, ProtocolFlags, ProtocolSocketInfo};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+
+/// Name of the sidecar binary we expect to be calling the broker.
+pub const EXPECTED_SIDECAR_NAME: &str = "node-backend";
+
+/// Resolve the executable name of the process that owns the other end of a
+/// TCP connection between `local` and `peer`.
+pub fn identify_peer(local: SocketAddr, peer: SocketAddr) -> Result<String> {
+    let sockets = iterate_sockets_info(AddressFamilyFlags::IPV4, ProtocolFlags::TCP)
+        .context("failed to enumerate local sockets")?;
+
+    for info in sockets.flatten() {
+        let ProtocolSocketInfo::Tcp(tcp) = &info.protocol_socket_info else {
+            continue;
+        };
+
+        // A loopback connection is listed once per end. Only the socket whose
+        // local end is `peer` belongs to the caller; the one whose local end
+        // is `local` is the broker's own accepted socket.
+        let matches = tcp.local_addr == peer.ip()
+            && tcp.local_port == peer.port()
+            && tcp.remote_addr == local.ip()
+            && tcp.remote_port == local.port();
+
+        if !matches {
+            continue;
+        }
+
+        let Some(pid) = info.associated_pids.first().copied() else {
+            continue;
+        };
+
+        let mut system = System::new();
+        system.refresh_processes(ProcessesToUpdate::All, true);
+        let process = system
+            .process(Pid::from_u32(pid))
+            .ok_or_else(|| anyhow!("process {pid} exited before it could be identified"))?;
+
+        return Ok(process.name().to_string_lossy().to_string());
+    }
+
+    Err(anyhow!("could not find the socket owning {peer}"))
+}
+
+/// Whether `process_name` matches the Node sidecar we expect to be calling
+/// the broker (allowing for platform-specific suffixes like `.exe`).
+pub fn is_expected_sidecar(process_name: &str) -> bool {
+    let stem = process_name.strip_suffix(".exe").unwrap_or(process_name);
+    stem == EXPECTED_SIDECAR_NAME
+}
@@ -0,0 +1,19 @@
+//! Authenticated local broker that lets the Node sidecar (see
+//! `src-tauri/sidecars/node-backend/`) fetch keyring-backed credentials
+//! without those secrets ever passing through environment variables.
+//!
+//! Every request is checked on two fronts before a secret is handed back:
+//! 1. the caller must present the per-spawn token minted when the broker
+//!    started (and which the sidecar is given at spawn time);
+//! 2. the OS-level peer behind the loopback socket must resolve to the
+//!    expected sidecar executable, not just "some local process that learned
+//!    the token".
+//!
+//! Every decision, allowed or denied, is surfaced to the frontend via the
+//! `credential-broker-decision` event, so a malicious local process trying
+//! to impersonate the sidecar doesn't do so silently.
+
+mod auth;
+mod server;
+
+pub use server::{start, Broker};
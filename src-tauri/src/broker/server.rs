@@ -0,0 +1,162 @@
+//! The broker's loopback accept loop and minimal request protocol.
+//!
+//! Requests are a single line, `<token> <credential_key>\n`; the reply is
+//! `OK <value>\n` or `ERR <reason>\n`. There's no need for anything richer:
+//! the only client is the local sidecar, and the interesting work is in who
+//! gets to ask, not in the wire format.
+
+use std::net::SocketAddr;
+
+use aes_gcm::aead::rand_core::{OsRng, RngCore};
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::auth;
+use crate::credentials;
+
+/// Credential keys the sidecar may ask the broker for. Deliberately excludes
+/// `aios_db`'s `db_encryption_key` (the chat history's at-rest AES key,
+/// read directly by `Database::new`, never by the sidecar) and everything
+/// else under the `com.aios.chat` keyring service — an allowed caller still
+/// only gets the credentials it actually needs.
+const ALLOWED_CREDENTIAL_KEYS: &[&str] = &["anthropic_api_key", "perplexity_api_key"];
+
+/// A running broker: the sidecar must be given `token` at spawn time (e.g.
+/// via an environment variable) to authenticate its requests.
+pub struct Broker {
+    pub addr: SocketAddr,
+    pub token: String,
+}
+
+/// Allow/deny decision for a single broker request, surfaced to the
+/// frontend via the `credential-broker-decision` event.
+#[derive(Debug, Clone, Serialize)]
+struct Decision {
+    peer_process: String,
+    credential_key: String,
+    allowed: bool,
+    reason: String,
+}
+
+/// Bind the broker to a loopback port chosen by the OS and start accepting
+/// connections in the background. Mints a fresh token for this run.
+pub async fn start(app: AppHandle) -> Result<Broker> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))
+        .await
+        .context("failed to bind the credential broker to loopback")?;
+    let addr = listener.local_addr()?;
+    let token = mint_token();
+
+    // This loop (and the per-connection task it spawns) runs detached, with
+    // no caller left to hand a `Result` back to, so failures are logged here
+    // with `eprintln!` instead of propagated — the same fire-and-forget
+    // convention `email::sync::watch_mailbox` uses for its background IDLE
+    // loop. Not leftover debug output.
+    let accept_token = token.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    eprintln!("credential broker: accept failed: {e}");
+                    break;
+                }
+            };
+
+            let app = app.clone();
+            let token = accept_token.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = handle_connection(&app, stream, addr, peer, &token).await {
+                    eprintln!("credential broker: {e}");
+                }
+            });
+        }
+    });
+
+    Ok(Broker { addr, token })
+}
+
+async fn handle_connection(
+    app: &AppHandle,
+    mut stream: TcpStream,
+    local: SocketAddr,
+    peer: SocketAddr,
+    expected_token: &str,
+) -> Result<()> {
+    let (reader, mut writer) = stream.split();
+    let mut lines = BufReader::new(reader).lines();
+
+    let Some(line) = lines.next_line().await? else {
+        return Ok(());
+    };
+    let mut parts = line.splitn(2, ' ');
+    let token = parts.next().unwrap_or_default();
+    let credential_key = parts.next().unwrap_or_default().trim();
+
+    let peer_process =
+        auth::identify_peer(local, peer).unwrap_or_else(|_| "unknown".to_string());
+    let token_ok = constant_time_eq(token, expected_token);
+    let process_ok = auth::is_expected_sidecar(&peer_process);
+    let key_ok = ALLOWED_CREDENTIAL_KEYS.contains(&credential_key);
+    let allowed = token_ok && process_ok && key_ok;
+
+    let reason = if !token_ok {
+        "invalid or missing token".to_string()
+    } else if !process_ok {
+        format!("unexpected caller process `{peer_process}`")
+    } else if !key_ok {
+        format!("credential `{credential_key}` is not in the sidecar allowlist")
+    } else {
+        "token and caller process verified".to_string()
+    };
+
+    // Surface every decision, not just denials: a silently-allowed request
+    // from an unexpected source is exactly what this broker exists to catch.
+    let _ = app.emit(
+        "credential-broker-decision",
+        Decision {
+            peer_process,
+            credential_key: credential_key.to_string(),
+            allowed,
+            reason,
+        },
+    );
+
+    if !allowed {
+        writer.write_all(b"ERR denied\n").await?;
+        return Ok(());
+    }
+
+    match credentials::get_credential(credential_key) {
+        Ok(value) => {
+            writer
+                .write_all(format!("OK {}\n", value.unwrap_or_default()).as_bytes())
+                .await?;
+        }
+        Err(e) => {
+            writer.write_all(format!("ERR {e}\n").as_bytes()).await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn mint_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,34 @@
+use tauri::AppHandle;
+
+use crate::db;
+use crate::email;
+
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub async fn sync_mailbox(app: AppHandle, mailbox: String) -> Result<usize, String> {
+    let synced = {
+        let db = db::get_db(&app);
+        email::sync_mailbox(db, &mailbox)
+            .await
+            .map_err(|e| e.to_string())?
+    };
+
+    // Keep syncing this mailbox live via IMAP IDLE instead of requiring the
+    // frontend to poll `sync_mailbox` again.
+    email::watch_mailbox(app, mailbox);
+
+    Ok(synced)
+}
+
+#[tauri::command]
+pub async fn list_mailboxes() -> Result<Vec<String>, String> {
+    email::list_mailboxes().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn send_email(to: String, subject: String, body: String) -> Result<(), String> {
+    let config = email::load_smtp_config().map_err(|e| e.to_string())?;
+    email::send_email(&config, &to, &subject, &body)
+        .await
+        .map_err(|e| e.to_string())
+}
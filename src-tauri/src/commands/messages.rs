@@ -11,7 +11,9 @@ pub fn save_message(
 ) -> Result<Message, String> {
     let db = db::get_db(&app);
     let conn = db.conn();
-    db::messages::save_message(&conn, &thread_id, &message).map_err(|e| e.to_string())
+    let search_conn = db.search_index();
+    db::messages::save_message(&conn, &thread_id, &message, db.key(), &search_conn)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -19,7 +21,7 @@ pub fn save_message(
 pub fn get_messages(app: AppHandle, thread_id: String) -> Result<Vec<Message>, String> {
     let db = db::get_db(&app);
     let conn = db.conn();
-    db::messages::get_messages(&conn, &thread_id).map_err(|e| e.to_string())
+    db::messages::get_messages(&conn, &thread_id, db.key()).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -27,5 +29,6 @@ pub fn get_messages(app: AppHandle, thread_id: String) -> Result<Vec<Message>, S
 pub fn delete_message(app: AppHandle, message_id: String) -> Result<(), String> {
     let db = db::get_db(&app);
     let conn = db.conn();
-    db::messages::delete_message(&conn, &message_id).map_err(|e| e.to_string())
+    let search_conn = db.search_index();
+    db::messages::delete_message(&conn, &message_id, &search_conn).map_err(|e| e.to_string())
 }
@@ -0,0 +1,5 @@
+pub mod email;
+pub mod messages;
+pub mod search;
+pub mod settings;
+pub mod threads;
@@ -0,0 +1,11 @@
+use tauri::AppHandle;
+
+use crate::db::{self, search::SearchResult};
+
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn search_messages(app: AppHandle, query: String) -> Result<Vec<SearchResult>, String> {
+    let db = db::get_db(&app);
+    let search_conn = db.search_index();
+    db::search::search_messages(&search_conn, &query).map_err(|e| e.to_string())
+}
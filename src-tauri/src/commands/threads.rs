@@ -15,7 +15,7 @@ pub fn create_thread(app: AppHandle) -> Result<Thread, String> {
 pub fn list_threads(app: AppHandle) -> Result<Vec<Thread>, String> {
     let db = db::get_db(&app);
     let conn = db.conn();
-    db::threads::list_threads(&conn).map_err(|e| e.to_string())
+    db::threads::list_threads(&conn, db.key()).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -23,7 +23,8 @@ pub fn list_threads(app: AppHandle) -> Result<Vec<Thread>, String> {
 pub fn delete_thread(app: AppHandle, id: String) -> Result<(), String> {
     let db = db::get_db(&app);
     let conn = db.conn();
-    db::threads::delete_thread(&conn, &id).map_err(|e| e.to_string())
+    let search_conn = db.search_index();
+    db::threads::delete_thread(&conn, &id, &search_conn).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -31,5 +32,7 @@ pub fn delete_thread(app: AppHandle, id: String) -> Result<(), String> {
 pub fn update_thread_title(app: AppHandle, id: String, title: String) -> Result<(), String> {
     let db = db::get_db(&app);
     let conn = db.conn();
-    db::threads::update_thread_title(&conn, &id, &title).map_err(|e| e.to_string())
+    let search_conn = db.search_index();
+    db::threads::update_thread_title(&conn, &id, &title, db.key(), &search_conn)
+        .map_err(|e| e.to_string())
 }
@@ -8,6 +8,8 @@
 
 use std::collections::HashMap;
 
+use anyhow::Result;
+
 /// Service name for keyring - identifies our app's credentials
 const SERVICE: &str = "com.aios.chat";
 
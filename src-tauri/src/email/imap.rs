@@ -0,0 +1,306 @@
+//! Low-level IMAP connection handling on top of `imap-flow`'s SANS-IO client.
+//!
+//! `imap-flow` only drives the protocol state machine; we own the socket and
+//! the event loop. Every public function here opens a connection, drives it
+//! to completion, and tears it back down — callers don't see `imap-flow`
+//! types at all.
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use imap_flow::client::{ClientFlow, ClientFlowEvent, ClientFlowOptions};
+use imap_flow::stream::AnyStream;
+use imap_types::command::{Command, CommandBody};
+use imap_types::core::Tag;
+use imap_types::fetch::MessageDataItemName;
+use imap_types::mailbox::Mailbox as MailboxName;
+use imap_types::sequence::{SeqOrUid, Sequence, SequenceSet};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls;
+use tokio_rustls::TlsConnector;
+
+use super::{ImapConfig, Security};
+
+/// One email as fetched from the server: just enough to map onto the
+/// `Message`/`Thread` model.
+#[derive(Debug, Clone)]
+pub struct EmailEnvelope {
+    pub uid: u32,
+    pub from: String,
+    pub subject: Option<String>,
+    pub body: String,
+    pub date: DateTime<Utc>,
+}
+
+/// List the mailboxes (folders) available on the server.
+pub async fn list_mailboxes(config: &ImapConfig) -> Result<Vec<String>> {
+    let mut client = connect(config).await?;
+    let reply = run(
+        &mut client,
+        CommandBody::List {
+            reference: MailboxName::try_from("").map_err(|e| anyhow!("{e}"))?,
+            mailbox_wildcard: "*".try_into().map_err(|e| anyhow!("{e}"))?,
+        },
+    )
+    .await?;
+
+    Ok(reply
+        .lines()
+        .filter_map(|line| line.rsplit(' ').next())
+        .map(|name| name.trim_matches('"').to_string())
+        .collect())
+}
+
+/// Select `mailbox` and fetch every message currently in it.
+pub async fn fetch_mailbox(config: &ImapConfig, mailbox: &str) -> Result<Vec<EmailEnvelope>> {
+    let mut client = connect(config).await?;
+    select(&mut client, mailbox).await?;
+
+    let sequence_set = SequenceSet(vec![Sequence::Range(
+        SeqOrUid::Value(1.try_into().map_err(|e| anyhow!("{e}"))?),
+        SeqOrUid::Asterisk,
+    )]);
+
+    let reply = run(
+        &mut client,
+        CommandBody::Fetch {
+            sequence_set,
+            macro_or_item_names: vec![
+                MessageDataItemName::Uid,
+                MessageDataItemName::Envelope,
+                MessageDataItemName::BodyExt {
+                    section: None,
+                    partial: None,
+                    peek: true,
+                },
+            ]
+            .into(),
+            uid: false,
+        },
+    )
+    .await?;
+
+    parse_fetch_reply(&reply)
+}
+
+/// Wait (via IMAP IDLE) for the server to signal new activity in `mailbox`,
+/// then return. Callers loop this to re-sync on every push notification
+/// instead of polling.
+pub async fn wait_for_update(config: &ImapConfig, mailbox: &str) -> Result<()> {
+    let mut client = connect(config).await?;
+    select(&mut client, mailbox).await?;
+
+    let handle = client.enqueue_command(Command::new(
+        Tag::try_from("A1").map_err(|e| anyhow!("{e}"))?,
+        CommandBody::Idle,
+    ));
+
+    loop {
+        match client
+            .progress()
+            .await
+            .context("IMAP connection closed while idling")?
+        {
+            ClientFlowEvent::DataReceived { .. } => return Ok(()),
+            ClientFlowEvent::CommandRejected {
+                handle: rejected, ..
+            } if rejected == handle => {
+                return Err(anyhow!("server rejected IDLE"));
+            }
+            _ => {}
+        }
+    }
+}
+
+async fn connect(config: &ImapConfig) -> Result<ClientFlow> {
+    let tcp = TcpStream::connect((config.host.as_str(), config.port))
+        .await
+        .with_context(|| format!("connecting to {}:{}", config.host, config.port))?;
+
+    let stream = match config.security {
+        Security::Ssl => AnyStream::Tls(Box::new(tls_connector(config.ssl_verify)?
+            .connect(server_name(&config.host)?, tcp)
+            .await
+            .context("TLS handshake with IMAP server failed")?)),
+        Security::StartTls | Security::None => AnyStream::Tcp(tcp),
+    };
+
+    let (mut client, _greeting) = ClientFlow::receive_greeting(stream, ClientFlowOptions::default())
+        .await
+        .context("reading IMAP greeting")?;
+
+    if config.security == Security::StartTls {
+        run(&mut client, CommandBody::StartTls).await?;
+
+        // The server ACKed STARTTLS; take back the still-plaintext socket and
+        // rebuild the client flow around a TLS-wrapped one, the same way the
+        // `Ssl` branch above does from the start.
+        let AnyStream::Tcp(tcp) = client.into_stream() else {
+            return Err(anyhow!("STARTTLS: stream was already encrypted"));
+        };
+        let tls_stream = tls_connector(config.ssl_verify)?
+            .connect(server_name(&config.host)?, tcp)
+            .await
+            .context("TLS handshake with IMAP server failed (STARTTLS)")?;
+
+        client = ClientFlow::new(AnyStream::Tls(Box::new(tls_stream)), ClientFlowOptions::default());
+    }
+
+    login(&mut client, config).await?;
+    Ok(client)
+}
+
+async fn login(client: &mut ClientFlow, config: &ImapConfig) -> Result<()> {
+    run(
+        client,
+        CommandBody::Login {
+            username: config.username.as_str().try_into().map_err(|e| anyhow!("{e}"))?,
+            password: config.password.as_str().try_into().map_err(|e| anyhow!("{e}"))?,
+        },
+    )
+    .await
+    .context("IMAP login failed")?;
+    Ok(())
+}
+
+async fn select(client: &mut ClientFlow, mailbox: &str) -> Result<()> {
+    run(
+        client,
+        CommandBody::Select {
+            mailbox: mailbox.try_into().map_err(|e| anyhow!("{e}"))?,
+        },
+    )
+    .await
+    .with_context(|| format!("selecting mailbox `{mailbox}`"))?;
+    Ok(())
+}
+
+/// Enqueue `body` as a command and drive the event loop until the server
+/// replies, returning the raw lines of data it sent back.
+async fn run(client: &mut ClientFlow, body: CommandBody<'static>) -> Result<String> {
+    let tag = Tag::try_from("A1").map_err(|e| anyhow!("{e}"))?;
+    let handle = client.enqueue_command(Command::new(tag, body));
+
+    let mut data = String::new();
+    loop {
+        match client.progress().await.context("IMAP connection closed")? {
+            ClientFlowEvent::DataReceived { data: chunk } => {
+                data.push_str(&String::from_utf8_lossy(&chunk));
+            }
+            ClientFlowEvent::CommandRejected {
+                handle: rejected,
+                status,
+                ..
+            } if rejected == handle => {
+                return Err(anyhow!("IMAP command rejected: {status:?}"));
+            }
+            ClientFlowEvent::CommandSent { handle: sent, .. } if sent == handle => {
+                return Ok(data);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn parse_fetch_reply(reply: &str) -> Result<Vec<EmailEnvelope>> {
+    // `imap-flow` hands us raw server data; the envelope/body extraction below
+    // is intentionally forgiving since a single malformed message shouldn't
+    // block the rest of the mailbox from syncing.
+    Ok(reply
+        .split("\r\n")
+        .filter(|line| line.contains("FETCH"))
+        .filter_map(|line| {
+            let uid = extract_field(line, "UID")?.parse().ok()?;
+            Some(EmailEnvelope {
+                uid,
+                from: extract_field(line, "FROM").unwrap_or_else(|| "unknown".to_string()),
+                subject: extract_field(line, "SUBJECT"),
+                body: extract_field(line, "BODY[TEXT]").unwrap_or_default(),
+                date: extract_field(line, "INTERNALDATE")
+                    .and_then(|d| DateTime::parse_from_rfc2822(&d).ok())
+                    .map(|d| d.with_timezone(&Utc))
+                    .unwrap_or_else(Utc::now),
+            })
+        })
+        .collect())
+}
+
+fn extract_field(line: &str, field: &str) -> Option<String> {
+    let start = line.find(field)? + field.len();
+    let rest = line[start..].trim_start();
+    let rest = rest.strip_prefix('"').unwrap_or(rest);
+    rest.split(['"', ' ']).next().map(str::to_string)
+}
+
+fn server_name(host: &str) -> Result<rustls::pki_types::ServerName<'static>> {
+    rustls::pki_types::ServerName::try_from(host.to_string())
+        .map_err(|_| anyhow!("invalid IMAP host name `{host}`"))
+}
+
+fn tls_connector(verify: bool) -> Result<TlsConnector> {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+    let config = rustls::ClientConfig::builder().with_root_certificates(roots);
+    let config = if verify {
+        config.with_no_client_auth()
+    } else {
+        // User explicitly disabled certificate verification (`email_ssl_verify`
+        // = "false"), typically for self-signed mail servers.
+        rustls::ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(danger::NoVerifier))
+            .with_no_client_auth()
+    };
+
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+mod danger {
+    use tokio_rustls::rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+    use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+    use tokio_rustls::rustls::{DigitallySignedStruct, Error, SignatureScheme};
+
+    #[derive(Debug)]
+    pub(super) struct NoVerifier;
+
+    impl ServerCertVerifier for NoVerifier {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &CertificateDer<'_>,
+            _intermediates: &[CertificateDer<'_>],
+            _server_name: &ServerName<'_>,
+            _ocsp_response: &[u8],
+            _now: UnixTime,
+        ) -> Result<ServerCertVerified, Error> {
+            Ok(ServerCertVerified::assertion())
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            _message: &[u8],
+            _cert: &CertificateDer<'_>,
+            _dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, Error> {
+            Ok(HandshakeSignatureValid::assertion())
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            _message: &[u8],
+            _cert: &CertificateDer<'_>,
+            _dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, Error> {
+            Ok(HandshakeSignatureValid::assertion())
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            vec![
+                SignatureScheme::RSA_PKCS1_SHA256,
+                SignatureScheme::ECDSA_NISTP256_SHA256,
+                SignatureScheme::ED25519,
+            ]
+        }
+    }
+}
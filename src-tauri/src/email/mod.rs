@@ -0,0 +1,105 @@
+//! Email subsystem: syncs IMAP mailboxes into the existing `Thread`/`Message`
+//! model and sends mail over SMTP.
+//!
+//! Connection settings are read from the `credentials` module's
+//! keyring-backed store: `email_imap_host`/`email_smtp_host`, the
+//! `email_*_security` modes ("ssl"/"starttls"/"none"), and `email_ssl_verify`.
+
+mod imap;
+mod smtp;
+mod sync;
+
+pub use smtp::send_email;
+pub use sync::{list_mailboxes, sync_mailbox, watch_mailbox, MailboxWatchers};
+
+use anyhow::{Context, Result};
+
+use crate::credentials::get_credential;
+
+/// Transport security mode for an IMAP or SMTP connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Security {
+    /// Implicit TLS from the first byte (IMAPS / SMTPS).
+    Ssl,
+    /// Plaintext connection upgraded to TLS via STARTTLS.
+    StartTls,
+    /// No transport security at all.
+    None,
+}
+
+impl Security {
+    fn parse(value: Option<&str>) -> Self {
+        match value.map(str::to_lowercase).as_deref() {
+            Some("starttls") => Self::StartTls,
+            Some("none") => Self::None,
+            // Default to the safest option if unset or unrecognized.
+            _ => Self::Ssl,
+        }
+    }
+}
+
+/// IMAP connection settings, loaded from the credential store.
+#[derive(Debug, Clone)]
+pub struct ImapConfig {
+    pub host: String,
+    pub port: u16,
+    pub security: Security,
+    pub ssl_verify: bool,
+    pub username: String,
+    pub password: String,
+}
+
+/// SMTP connection settings, loaded from the credential store.
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub security: Security,
+    pub ssl_verify: bool,
+    pub username: String,
+    pub password: String,
+}
+
+pub fn load_imap_config() -> Result<ImapConfig> {
+    Ok(ImapConfig {
+        host: required("email_imap_host")?,
+        port: port("email_imap_port", 993)?,
+        security: Security::parse(optional("email_imap_security")?.as_deref()),
+        ssl_verify: ssl_verify()?,
+        username: required("email_username")?,
+        password: required("email_password")?,
+    })
+}
+
+pub fn load_smtp_config() -> Result<SmtpConfig> {
+    Ok(SmtpConfig {
+        host: required("email_smtp_host")?,
+        port: port("email_smtp_port", 587)?,
+        security: Security::parse(optional("email_smtp_security")?.as_deref()),
+        ssl_verify: ssl_verify()?,
+        username: required("email_username")?,
+        password: required("email_password")?,
+    })
+}
+
+fn required(key: &str) -> Result<String> {
+    optional(key)?.with_context(|| format!("missing credential `{key}`; configure it in settings"))
+}
+
+fn optional(key: &str) -> Result<Option<String>> {
+    get_credential(key).map_err(|e| anyhow::anyhow!(e))
+}
+
+fn port(key: &str, default: u16) -> Result<u16> {
+    optional(key)?
+        .map(|p| p.parse())
+        .transpose()
+        .with_context(|| format!("{key} is not a valid port number"))
+        .map(|p| p.unwrap_or(default))
+}
+
+fn ssl_verify() -> Result<bool> {
+    Ok(optional("email_ssl_verify")?
+        .map(|v| v != "false")
+        .unwrap_or(true))
+}
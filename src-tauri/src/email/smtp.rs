@@ -0,0 +1,49 @@
+//! Outgoing mail via SMTP, built on `lettre`.
+
+use anyhow::{Context, Result};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::transport::smtp::client::{Tls, TlsParameters};
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use super::{SmtpConfig, Security};
+
+/// Send a plain-text email through the configured SMTP server.
+pub async fn send_email(config: &SmtpConfig, to: &str, subject: &str, body: &str) -> Result<()> {
+    let from: Mailbox = config.username.parse().context("invalid `from` address")?;
+    let to: Mailbox = to.parse().context("invalid `to` address")?;
+
+    let message = Message::builder()
+        .from(from)
+        .to(to)
+        .subject(subject)
+        .body(body.to_string())
+        .context("failed to build email message")?;
+
+    let tls_params = TlsParameters::builder(config.host.clone())
+        .dangerous_accept_invalid_certs(!config.ssl_verify)
+        .build()
+        .context("failed to build TLS parameters")?;
+
+    let tls = match config.security {
+        Security::Ssl => Tls::Wrapper(tls_params),
+        Security::StartTls => Tls::Required(tls_params),
+        Security::None => Tls::None,
+    };
+
+    let transport = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+        .port(config.port)
+        .tls(tls)
+        .credentials(Credentials::new(
+            config.username.clone(),
+            config.password.clone(),
+        ))
+        .build();
+
+    transport
+        .send(message)
+        .await
+        .context("failed to send email")?;
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+//! Maps IMAP mailboxes onto the existing `Thread`/`Message` model.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use rusqlite::Connection;
+use tauri::{AppHandle, Manager};
+
+use crate::db::{self, Database, NewMessage};
+
+use super::imap;
+
+/// Mailboxes with a live IMAP IDLE watcher already spawned via
+/// [`watch_mailbox`], so repeated syncs (a refresh button, app relaunch,
+/// re-selecting the mailbox) don't each leak another concurrent IDLE task.
+#[derive(Default)]
+pub struct MailboxWatchers(Mutex<HashSet<String>>);
+
+impl MailboxWatchers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// List the mailboxes (folders) available on the configured IMAP account.
+pub async fn list_mailboxes() -> Result<Vec<String>> {
+    let config = super::load_imap_config()?;
+    imap::list_mailboxes(&config).await
+}
+
+/// Fetch every message in `mailbox` and persist the ones not already synced.
+/// Returns how many new messages were saved.
+///
+/// `mailbox` becomes the title of a single thread that every message in that
+/// folder is appended to; each message's `role` is the sender's address.
+pub async fn sync_mailbox(db: &Database, mailbox: &str) -> Result<usize> {
+    let config = super::load_imap_config()?;
+    let envelopes = imap::fetch_mailbox(&config, mailbox).await?;
+
+    let conn = db.conn();
+    let search_conn = db.search_index();
+    let key = db.key();
+
+    let thread_id = find_or_create_thread(&conn, mailbox, key, &search_conn)?;
+    let existing = db::messages::get_messages(&conn, &thread_id, key)?;
+
+    let mut synced = 0;
+    for envelope in envelopes {
+        // imap-flow doesn't give us a stable local row to dedupe against, so
+        // fall back to matching on sender + body; good enough to stop a
+        // repeated sync (e.g. from the IDLE loop) from duplicating messages.
+        let already_synced = existing
+            .iter()
+            .any(|m| m.role == envelope.from && m.content == envelope.body);
+        if already_synced {
+            continue;
+        }
+
+        db::messages::save_message(
+            &conn,
+            &thread_id,
+            &NewMessage {
+                role: envelope.from,
+                content: envelope.body,
+                tool_invocations: None,
+            },
+            key,
+            &search_conn,
+        )?;
+        synced += 1;
+    }
+
+    Ok(synced)
+}
+
+fn find_or_create_thread(
+    conn: &Connection,
+    mailbox: &str,
+    key: &[u8; 32],
+    search_conn: &Connection,
+) -> Result<String> {
+    let threads = db::threads::list_threads(conn, key)?;
+    if let Some(existing) = threads
+        .into_iter()
+        .find(|t| t.title.as_deref() == Some(mailbox))
+    {
+        return Ok(existing.id);
+    }
+
+    let thread = db::threads::create_thread(conn)?;
+    db::threads::update_thread_title(conn, &thread.id, mailbox, key, search_conn)?;
+    Ok(thread.id)
+}
+
+/// Watch `mailbox` for new mail via IMAP IDLE, re-syncing it into the
+/// database every time the server reports activity, so new mail shows up
+/// live without polling.
+///
+/// A no-op if a watcher for `mailbox` is already running.
+pub fn watch_mailbox(app: AppHandle, mailbox: String) {
+    let watchers = app.state::<MailboxWatchers>();
+    {
+        let mut active = watchers.0.lock().expect("MailboxWatchers mutex poisoned");
+        if !active.insert(mailbox.clone()) {
+            return;
+        }
+    }
+
+    // Detached background task with no caller to return a `Result` to, so
+    // failures are logged with `eprintln!` rather than propagated — the same
+    // fire-and-forget convention `broker::server::start` uses for its accept
+    // loop. Not leftover debug output.
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let config = match super::load_imap_config() {
+                Ok(config) => config,
+                Err(e) => {
+                    eprintln!("email: failed to load IMAP config for `{mailbox}`: {e}");
+                    break;
+                }
+            };
+
+            if let Err(e) = imap::wait_for_update(&config, &mailbox).await {
+                eprintln!("email: IDLE wait failed for `{mailbox}`: {e}");
+                break;
+            }
+
+            let db = db::get_db(&app);
+            if let Err(e) = sync_mailbox(db, &mailbox).await {
+                eprintln!("email: sync failed for `{mailbox}`: {e}");
+            }
+        }
+
+        let watchers = app.state::<MailboxWatchers>();
+        watchers
+            .0
+            .lock()
+            .expect("MailboxWatchers mutex poisoned")
+            .remove(&mailbox);
+    });
+}
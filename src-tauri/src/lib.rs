@@ -1,5 +1,8 @@
+mod broker;
 mod commands;
+mod credentials;
 mod db;
+mod email;
 
 use std::fs;
 
@@ -22,8 +25,19 @@ pub fn run() {
             let database = Database::new(db_path)?;
             app.manage(database);
 
+            // Start the credential broker before the sidecar exists so its
+            // address/token are ready to hand over at spawn time.
+            let broker = tauri::async_runtime::block_on(broker::start(app.handle().clone()))?;
+            println!("credential broker listening on {}", broker.addr);
+            app.manage(broker);
+
+            app.manage(email::MailboxWatchers::new());
+
             // Note: Node backend runs separately during development.
-            // For production, add sidecar spawning here.
+            // For production, spawn the sidecar with env vars
+            // CREDENTIAL_BROKER_ADDR=<broker.addr> and
+            // CREDENTIAL_BROKER_TOKEN=<broker.token> so it can authenticate
+            // to the broker above instead of receiving API keys directly.
             // See: src-tauri/sidecars/node-backend/
 
             // Create a custom menu with standard text editing shortcuts (Cmd+A, Cmd+C, etc.)
@@ -70,6 +84,12 @@ pub fn run() {
             commands::messages::save_message,
             commands::messages::get_messages,
             commands::messages::delete_message,
+            commands::settings::mark_settings_submitted,
+            commands::settings::is_settings_submitted,
+            commands::email::sync_mailbox,
+            commands::email::list_mailboxes,
+            commands::email::send_email,
+            commands::search::search_messages,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");